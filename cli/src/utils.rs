@@ -3,27 +3,159 @@ use ethers::{
     prelude::TransactionReceipt,
     providers::Middleware,
     types::U256,
-    utils::{format_units, to_checksum},
+    utils::{format_units, hex, keccak256, to_checksum},
 };
 use eyre::Result;
 use foundry_config::{Chain, Config};
+use serde::Deserialize;
 use std::{
+    collections::{BTreeMap, HashSet},
     ffi::OsStr,
     future::Future,
     ops::Mul,
     path::{Path, PathBuf},
     process::{Command, Output, Stdio},
     str::FromStr,
+    sync::OnceLock,
     time::Duration,
 };
 use tracing_error::ErrorLayer;
 use tracing_subscriber::prelude::*;
 use yansi::Paint;
 
+/// Which git implementation [`Git`] should use to talk to the underlying repository.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GitBackend {
+    /// Always shell out to the system `git` binary.
+    Cli,
+    /// Use the bundled `git2` (libgit2) bindings for `clone`, `checkout`, `commit`, `is_clean`,
+    /// `commit_hash`, `has_missing_dependencies`, `submodule_add` and `submodule_update`.
+    ///
+    /// `init`, `add`, `rm`, `is_in_repo`, `has_branch`, `tag` and `root_of` have no libgit2
+    /// implementation and still spawn the `git` binary with this backend.
+    Libgit2,
+    /// Use [`GitBackend::Cli`] if a `git` binary is found on `PATH`, otherwise fall back to
+    /// [`GitBackend::Libgit2`].
+    ///
+    /// This keeps behavior unchanged on systems that already have `git` installed. Without one,
+    /// only the operations listed under [`GitBackend::Libgit2`] work.
+    #[default]
+    Auto,
+}
+
+impl GitBackend {
+    /// Resolves [`GitBackend::Auto`] to either [`GitBackend::Cli`] or [`GitBackend::Libgit2`],
+    /// leaving an explicit choice untouched.
+    ///
+    /// The `git`-on-`PATH` probe is only ever run once per process and cached, so resolving
+    /// `Auto` doesn't double the number of spawned processes for the common case where `git` is
+    /// installed.
+    fn resolve(self) -> Self {
+        static GIT_ON_PATH: OnceLock<bool> = OnceLock::new();
+        match self {
+            Self::Auto => {
+                let git_on_path = *GIT_ON_PATH.get_or_init(|| {
+                    Git::cmd_no_root()
+                        .arg("--version")
+                        .output()
+                        .map_or(false, |o| o.status.success())
+                });
+                if git_on_path {
+                    Self::Cli
+                } else {
+                    Self::Libgit2
+                }
+            }
+            explicit => explicit,
+        }
+    }
+}
+
 // reexport all `foundry_config::utils`
 #[doc(hidden)]
 pub use foundry_config::utils::*;
 
+/// A single entry of the `[alias]` table in `foundry.toml`.
+///
+/// Mirrors Cargo's `alias.<name>` mechanism: an alias can either be a single string that's split
+/// on whitespace, e.g. `tb = "build --sizes"`, or an explicit list of tokens, e.g.
+/// `cov = ["test", "--gas-report"]`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum AliasValue {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl AliasValue {
+    fn tokens(&self) -> Vec<String> {
+        match self {
+            Self::Single(s) => s.split_whitespace().map(str::to_string).collect(),
+            Self::Multiple(v) => v.clone(),
+        }
+    }
+}
+
+/// Resolves user-defined command aliases from the `[alias]` table in `foundry.toml` before
+/// `clap` gets a chance to parse `args`.
+///
+/// The first positional token (the subcommand) is looked up in `aliases`; if found, its expanded
+/// tokens are spliced into `args` in its place. This runs iteratively so that an alias may expand
+/// to another alias, bailing with an error if a cycle is detected. A name listed in
+/// `known_subcommands` always takes precedence over a same-named alias, so aliases can never
+/// shadow a built-in command.
+pub fn resolve_aliases(
+    mut args: Vec<String>,
+    aliases: &BTreeMap<String, AliasValue>,
+    known_subcommands: &[&str],
+    value_flags: &[&str],
+) -> Result<Vec<String>> {
+    if aliases.is_empty() {
+        return Ok(args)
+    }
+
+    let mut visited = HashSet::new();
+    loop {
+        let Some(pos) = find_subcommand_pos(&args, value_flags) else { return Ok(args) };
+
+        let name = &args[pos];
+        if known_subcommands.contains(&name.as_str()) {
+            return Ok(args)
+        }
+        let Some(alias) = aliases.get(name) else { return Ok(args) };
+
+        if !visited.insert(name.clone()) {
+            eyre::bail!(
+                "encountered a cycle resolving alias `{name}`; check the [alias] table in \
+                 foundry.toml"
+            )
+        }
+
+        args.splice(pos..=pos, alias.tokens());
+    }
+}
+
+/// Finds the index of the first positional token in `args` (skipping the binary name at index
+/// `0`), i.e. the subcommand `clap` would otherwise parse.
+///
+/// Global flags that consume a following value token (listed in `value_flags`, e.g. `--config`)
+/// have that value token skipped too, so it's never mistaken for the subcommand; a `--flag=value`
+/// form carries its value inline and needs no such skip.
+fn find_subcommand_pos(args: &[String], value_flags: &[&str]) -> Option<usize> {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = &args[i];
+        if !arg.starts_with('-') {
+            return Some(i)
+        }
+        if !arg.contains('=') && value_flags.contains(&arg.as_str()) {
+            i += 1;
+        }
+        i += 1;
+    }
+    None
+}
+
 /// The version message for the current program, like
 /// `forge 0.1.0 (f01b232bc 2022-01-22T23:28:39.493201+00:00)`
 pub(crate) const VERSION_MESSAGE: &str = concat!(
@@ -73,6 +205,117 @@ impl<T: AsRef<Path>> FoundryPathExt for T {
     }
 }
 
+/// Content-addressed record of a compiled unit's source dependencies, persisted to a
+/// `dep-info`-style sidecar file so that `forge`/`cast` can cheaply decide whether an expensive
+/// recompile or artifact regeneration can be skipped, instead of relying solely on mtimes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fingerprint {
+    entries: Vec<(PathBuf, String)>,
+}
+
+impl Fingerprint {
+    /// Hashes the contents of `paths` into a new, not-yet-persisted [`Fingerprint`].
+    pub fn new<I, P>(paths: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let entries = paths
+            .into_iter()
+            .map(|p| {
+                let path = p.as_ref().to_path_buf();
+                let hash = Self::hash_file(&path)?;
+                Ok((path, hash))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { entries })
+    }
+
+    /// Loads a previously persisted fingerprint from `path`, or `None` if it doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Self::parse_dep_info(&contents).map(Some)
+    }
+
+    /// Persists this fingerprint to `path` as a dep-info-style sidecar file: one `<hash> <path>`
+    /// record per physical line, with any literal `\` or ` ` in the path backslash-escaped.
+    pub fn persist(&self, path: &Path) -> Result<()> {
+        let mut out = String::new();
+        for (dep_path, hash) in &self.entries {
+            out.push_str(hash);
+            out.push(' ');
+            out.push_str(&escape_dep_info_path(&dep_path.to_string_lossy()));
+            out.push('\n');
+        }
+        Ok(foundry_common::fs::write(path, out)?)
+    }
+
+    /// Returns `true` if every recorded dependency still exists and hashes to the same value.
+    pub fn is_fresh(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|(path, hash)| Self::hash_file(path).map_or(false, |h| h == *hash))
+    }
+
+    /// Parses a dep-info-style sidecar file: one `<hash> <path>` record per physical line, with
+    /// the path's `\`-escapes (as written by [`persist`](Self::persist)) undone exactly as
+    /// Cargo's `parse_dep_info` un-escapes a dep-info path. A dangling trailing `\` with nothing
+    /// following it is a hard error rather than being silently dropped.
+    fn parse_dep_info(contents: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+
+        for line in contents.lines() {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue
+            }
+
+            let (hash, path) = line
+                .split_once(' ')
+                .ok_or_else(|| eyre::eyre!("malformed dep-info line: {line:?}"))?;
+            let path = unescape_dep_info_path(path)?;
+            if path.is_empty() {
+                eyre::bail!("malformed dep-info line: {line:?}");
+            }
+            entries.push((PathBuf::from(path), hash.to_string()));
+        }
+
+        Ok(Self { entries })
+    }
+
+    fn hash_file(path: &Path) -> Result<String> {
+        let contents = std::fs::read(path)?;
+        Ok(hex::encode(keccak256(contents)))
+    }
+}
+
+/// Backslash-escapes `\` and ` ` in a dep-info path, the inverse of [`unescape_dep_info_path`].
+fn escape_dep_info_path(path: &str) -> String {
+    path.replace('\\', "\\\\").replace(' ', "\\ ")
+}
+
+/// Un-escapes a dep-info path written by [`escape_dep_info_path`]: each `\x` becomes a literal
+/// `x`. A trailing `\` with no following character is malformed and a hard error.
+fn unescape_dep_info_path(path: &str) -> Result<String> {
+    let mut out = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some(escaped) => out.push(escaped),
+                None => eyre::bail!("dep-info path ends with a dangling `\\` escape"),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    Ok(out)
+}
+
 /// Initializes a tracing Subscriber for logging
 #[allow(dead_code)]
 pub fn subscriber() {
@@ -105,6 +348,258 @@ pub fn get_provider_builder(config: &Config) -> Result<foundry_common::ProviderB
     Ok(foundry_common::ProviderBuilder::new(url.as_ref()).chain(chain))
 }
 
+/// Resolves the active AWS profile's credentials and warns (via [`warn_if_expiring`]) when they
+/// are close to expiring, so long broadcast/scripting runs surface impending credential expiry
+/// before a transaction is dropped mid-way through.
+///
+/// Callers on the AWS KMS signer-selection path (i.e. only once an AWS-backed signer has actually
+/// been chosen, e.g. via an `--aws` wallet flag) should invoke this once before broadcasting.
+/// This must not be called from the generic provider/RPC path: every `forge`/`cast` invocation
+/// goes through that path, including plain reads with no AWS signer involved, and an ambient
+/// `AWS_PROFILE` left over from unrelated shell work would otherwise trigger spurious warnings
+/// (and, for `credential_process` profiles, an unwanted subprocess spawn) on every call.
+pub fn warn_if_aws_signer_credentials_expiring() -> Result<()> {
+    if let Some(creds) = resolve_aws_credentials()? {
+        warn_if_expiring(&creds, DEFAULT_CREDENTIAL_EXPIRY_WARNING);
+    }
+    Ok(())
+}
+
+/// Remaining lifetime under which a temporary AWS credential is considered "about to expire" and
+/// surfaced via [`warn_if_expiring`].
+pub const DEFAULT_CREDENTIAL_EXPIRY_WARNING: Duration = Duration::from_secs(5 * 60);
+
+/// A resolved set of AWS credentials, as read from the environment, a named profile, or a
+/// `credential_process`.
+#[derive(Clone, Debug)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+    /// Only set for temporary (session) credentials.
+    pub expiration: Option<std::time::SystemTime>,
+}
+
+impl AwsCredentials {
+    /// Remaining lifetime of temporary credentials, or `None` for long-lived ones.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.expiration.map(|expiration| {
+            expiration.duration_since(std::time::SystemTime::now()).unwrap_or_default()
+        })
+    }
+}
+
+/// Resolves AWS credentials the way the cloud SDKs do: the `AWS_PROFILE` environment variable
+/// (falling back to the `default` profile) read from `~/.aws/config`/`~/.aws/credentials` --
+/// including `credential_process` entries -- with
+/// `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`/`AWS_SESSION_TOKEN` environment variables taking
+/// precedence over all of the above.
+///
+/// A profile with an `sso_start_url` entry yields no credentials: it only logs a warning that the
+/// SSO login has to be completed outside Foundry.
+pub fn resolve_aws_credentials() -> Result<Option<AwsCredentials>> {
+    if let (Ok(access_key_id), Ok(secret_access_key)) =
+        (std::env::var("AWS_ACCESS_KEY_ID"), std::env::var("AWS_SECRET_ACCESS_KEY"))
+    {
+        let session_token = std::env::var("AWS_SESSION_TOKEN").ok();
+        let expiration =
+            std::env::var("AWS_SESSION_EXPIRATION").ok().and_then(|s| parse_rfc3339(&s));
+        return Ok(Some(AwsCredentials {
+            access_key_id,
+            secret_access_key,
+            session_token,
+            expiration,
+        }))
+    }
+
+    let profile = std::env::var("AWS_PROFILE").unwrap_or_else(|_| "default".to_string());
+    resolve_aws_profile_credentials(&profile)
+}
+
+fn resolve_aws_profile_credentials(profile: &str) -> Result<Option<AwsCredentials>> {
+    let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) else {
+        return Ok(None)
+    };
+    let home = PathBuf::from(home);
+
+    let mut entries = std::fs::read_to_string(home.join(".aws/credentials"))
+        .ok()
+        .map(|contents| parse_ini_section(&contents, profile))
+        .unwrap_or_default();
+
+    if entries.is_empty() {
+        let section =
+            if profile == "default" { "default".to_string() } else { format!("profile {profile}") };
+        entries = std::fs::read_to_string(home.join(".aws/config"))
+            .ok()
+            .map(|contents| parse_ini_section(&contents, &section))
+            .unwrap_or_default();
+    }
+
+    if entries.is_empty() {
+        return Ok(None)
+    }
+
+    if let Some(command) = entries.get("credential_process") {
+        return resolve_credential_process(command).map(Some)
+    }
+
+    if let Some(start_url) = entries.get("sso_start_url") {
+        tracing::warn!(
+            profile,
+            start_url,
+            "SSO profiles require an interactive browser login (`aws sso login`); Foundry cannot \
+             complete the SSO flow itself"
+        );
+        return Ok(None)
+    }
+
+    let (Some(access_key_id), Some(secret_access_key)) =
+        (entries.get("aws_access_key_id"), entries.get("aws_secret_access_key"))
+    else {
+        return Ok(None)
+    };
+
+    Ok(Some(AwsCredentials {
+        access_key_id: access_key_id.clone(),
+        secret_access_key: secret_access_key.clone(),
+        session_token: entries.get("aws_session_token").cloned(),
+        expiration: None,
+    }))
+}
+
+/// Runs a `credential_process` entry and parses its `AccessKeyId`/`SecretAccessKey`/
+/// `SessionToken`/`Expiration` JSON output, per the shared credential process spec the cloud SDKs
+/// implement.
+fn resolve_credential_process(command: &str) -> Result<AwsCredentials> {
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", command]).get_stdout_lossy()?
+    } else {
+        Command::new("sh").args(["-c", command]).get_stdout_lossy()?
+    };
+
+    let value: serde_json::Value = serde_json::from_str(&output)?;
+    let field = |key: &str| -> Result<String> {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| eyre::eyre!("credential_process output missing `{key}`"))
+    };
+
+    Ok(AwsCredentials {
+        access_key_id: field("AccessKeyId")?,
+        secret_access_key: field("SecretAccessKey")?,
+        session_token: value.get("SessionToken").and_then(|v| v.as_str()).map(str::to_string),
+        expiration: value.get("Expiration").and_then(|v| v.as_str()).and_then(parse_rfc3339),
+    })
+}
+
+/// A tiny INI-style section reader, just enough for `~/.aws/config`/`~/.aws/credentials`: no
+/// nesting or escaping, `key = value` pairs grouped under `[section]` headers.
+fn parse_ini_section(contents: &str, section: &str) -> BTreeMap<String, String> {
+    let mut out = BTreeMap::new();
+    let mut in_section = false;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue
+        }
+        if let Some(header) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            in_section = header.trim() == section;
+            continue
+        }
+        if in_section {
+            if let Some((key, value)) = line.split_once('=') {
+                out.insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+    }
+    out
+}
+
+/// Parses an RFC3339 timestamp (as emitted by AWS, e.g. `2024-01-01T12:00:00Z`, or by
+/// `credential_process` helpers as `2024-01-01T12:00:00+00:00`) without pulling in a date/time
+/// crate.
+fn parse_rfc3339(s: &str) -> Option<std::time::SystemTime> {
+    let s = s.trim();
+    let t_pos = s.find('T')?;
+    let (s, offset_secs) = match s.strip_suffix('Z') {
+        Some(s) => (s, 0),
+        None => {
+            // The date portion also contains `-`, so only look for a sign past the `T`.
+            let sign_pos = t_pos + s[t_pos..].find(['+', '-'])?;
+            let (rest, offset) = s.split_at(sign_pos);
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let (oh, om) = offset[1..].split_once(':')?;
+            let oh: i64 = oh.parse().ok()?;
+            let om: i64 = om.parse().ok()?;
+            (rest, sign * (oh * 3600 + om * 60))
+        }
+    };
+    let (date, time) = s.split_once('T')?;
+
+    let mut date_parts = date.split('-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: u32 = date_parts.next()?.parse().ok()?;
+    let day: u32 = date_parts.next()?.parse().ok()?;
+
+    let time = time.split('.').next()?; // drop fractional seconds, if any
+    let mut time_parts = time.split(':');
+    let hour: i64 = time_parts.next()?.parse().ok()?;
+    let minute: i64 = time_parts.next()?.parse().ok()?;
+    let second: i64 = time_parts.next()?.parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    (secs >= 0)
+        .then(|| std::time::SystemTime::UNIX_EPOCH + Duration::from_secs(secs as u64))
+}
+
+/// Howard Hinnant's `days_from_civil` algorithm: turns a Gregorian date into a day count relative
+/// to the Unix epoch, used by [`parse_rfc3339`] instead of pulling in a date/time dependency.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (i64::from(m) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Emits a `tracing::warn!` plus a human-readable countdown when temporary credentials are within
+/// `threshold` of expiring, so long broadcast/scripting runs surface impending credential expiry
+/// before a transaction is dropped.
+pub fn warn_if_expiring(creds: &AwsCredentials, threshold: Duration) {
+    let Some(remaining) = creds.remaining() else { return };
+    if remaining > threshold {
+        return
+    }
+
+    tracing::warn!(
+        remaining_secs = remaining.as_secs(),
+        "AWS session credentials are expiring soon"
+    );
+    println!(
+        "\n⚠️  AWS session credentials expire in {} -- broadcast/scripting runs may start failing \
+         with dropped transactions once they do.",
+        format_countdown(remaining)
+    );
+}
+
+/// Formats a [`Duration`] as a terse `1h 2m 3s`-style countdown.
+fn format_countdown(d: Duration) -> String {
+    let total = d.as_secs();
+    let (h, rem) = (total / 3600, total % 3600);
+    let (m, s) = (rem / 60, rem % 60);
+    match (h, m) {
+        (0, 0) => format!("{s}s"),
+        (0, _) => format!("{m}m {s}s"),
+        _ => format!("{h}h {m}m {s}s"),
+    }
+}
+
 pub async fn get_chain<M>(chain: Option<Chain>, provider: M) -> Result<Chain>
 where
     M: Middleware,
@@ -302,12 +797,13 @@ pub struct Git<'a> {
     pub root: &'a Path,
     pub quiet: bool,
     pub shallow: bool,
+    pub backend: GitBackend,
 }
 
 impl<'a> Git<'a> {
     #[inline]
     pub fn new(root: &'a Path) -> Self {
-        Self { root, quiet: false, shallow: false }
+        Self { root, quiet: false, shallow: false, backend: GitBackend::default() }
     }
 
     #[inline]
@@ -324,10 +820,14 @@ impl<'a> Git<'a> {
     }
 
     pub fn clone(
+        backend: GitBackend,
         shallow: bool,
         from: impl AsRef<OsStr>,
         to: Option<impl AsRef<OsStr>>,
     ) -> Result<()> {
+        if let GitBackend::Libgit2 = backend.resolve() {
+            return Self::clone_libgit2(shallow, from.as_ref(), to.as_ref().map(AsRef::as_ref))
+        }
         Self::cmd_no_root()
             .stderr(Stdio::inherit())
             .args(["clone", "--recurse-submodules"])
@@ -339,6 +839,59 @@ impl<'a> Git<'a> {
             .map(drop)
     }
 
+    fn clone_libgit2(shallow: bool, from: &OsStr, to: Option<&OsStr>) -> Result<()> {
+        let from = from.to_str().ok_or_else(|| eyre::eyre!("remote url is not valid UTF-8"))?;
+        let to = to
+            .map(|to| to.to_str().ok_or_else(|| eyre::eyre!("target path is not valid UTF-8")))
+            .transpose()?
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                let name = from.rsplit('/').next().unwrap_or(from);
+                PathBuf::from(name.strip_suffix(".git").unwrap_or(name))
+            });
+
+        let repo = git2::build::RepoBuilder::new()
+            .fetch_options(Self::fetch_options(shallow))
+            .clone(from, &to)?;
+
+        let mut update_options = git2::SubmoduleUpdateOptions::new();
+        update_options.fetch(Self::fetch_options(shallow));
+        for mut submodule in repo.submodules()? {
+            submodule.update(true, Some(&mut update_options))?;
+        }
+        Ok(())
+    }
+
+    /// Credential callbacks shared by all `git2` network operations: an SSH key from the agent
+    /// (when the URL carries a username), then the default credentials, then the configured git
+    /// credential helpers. `.netrc` is not consulted.
+    fn remote_callbacks() -> git2::RemoteCallbacks<'static> {
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.credentials(|url, username_from_url, allowed_types| {
+            if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+                if let Some(username) = username_from_url {
+                    if let Ok(cred) = git2::Cred::ssh_key_from_agent(username) {
+                        return Ok(cred)
+                    }
+                }
+            }
+            if allowed_types.contains(git2::CredentialType::DEFAULT) {
+                return git2::Cred::default()
+            }
+            git2::Cred::credential_helper(&git2::Config::open_default()?, url, username_from_url)
+        });
+        callbacks
+    }
+
+    fn fetch_options(shallow: bool) -> git2::FetchOptions<'static> {
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(Self::remote_callbacks());
+        if shallow {
+            fetch_options.depth(1);
+        }
+        fetch_options
+    }
+
     #[inline]
     pub fn root(self, root: &Path) -> Git<'_> {
         Git { root, ..self }
@@ -355,7 +908,16 @@ impl<'a> Git<'a> {
         Self { shallow, ..self }
     }
 
+    /// Selects which git implementation to use for this instance's operations.
+    #[inline]
+    pub fn backend(self, backend: GitBackend) -> Self {
+        Self { backend, ..self }
+    }
+
     pub fn checkout(self, recursive: bool, tag: impl AsRef<OsStr>) -> Result<()> {
+        if let GitBackend::Libgit2 = self.backend.resolve() {
+            return self.checkout_libgit2(recursive, tag.as_ref())
+        }
         self.cmd()
             .arg("checkout")
             .args(recursive.then_some("--recurse-submodules"))
@@ -364,6 +926,28 @@ impl<'a> Git<'a> {
             .map(drop)
     }
 
+    fn checkout_libgit2(self, recursive: bool, tag: &OsStr) -> Result<()> {
+        let repo = git2::Repository::open(self.root)?;
+        let tag = tag.to_str().ok_or_else(|| eyre::eyre!("tag is not valid UTF-8"))?;
+        let (object, reference) = repo.revparse_ext(tag)?;
+        // Intentionally not `.force()`-ed: like the plain `git checkout` the `Command`-based path
+        // shells out to, this errors out instead of clobbering uncommitted local changes.
+        repo.checkout_tree(&object, Some(&mut git2::build::CheckoutBuilder::new()))?;
+        // Only a branch leaves HEAD symbolic, matching `git checkout`: checking out a tag (or any
+        // other non-branch ref) detaches HEAD at the resolved commit instead, so that a later
+        // `commit()` creates a detached commit rather than moving the tag.
+        match reference.filter(|r| r.is_branch()) {
+            Some(branch) => {
+                repo.set_head(branch.name().ok_or_else(|| eyre::eyre!("invalid reference name"))?)?
+            }
+            None => repo.set_head_detached(object.id())?,
+        }
+        if recursive {
+            self.submodule_update_libgit2(false, false, std::iter::empty::<&str>())?;
+        }
+        Ok(())
+    }
+
     pub fn init(self) -> Result<()> {
         self.cmd().arg("init").exec().map(drop)
     }
@@ -386,6 +970,9 @@ impl<'a> Git<'a> {
     }
 
     pub fn commit(self, msg: &str) -> Result<()> {
+        if let GitBackend::Libgit2 = self.backend.resolve() {
+            return self.commit_libgit2(msg)
+        }
         let output = self
             .cmd()
             .args(["commit", "-m", msg])
@@ -408,11 +995,40 @@ impl<'a> Git<'a> {
         Ok(())
     }
 
+    fn commit_libgit2(self, msg: &str) -> Result<()> {
+        let repo = git2::Repository::open(self.root)?;
+        let mut index = repo.index()?;
+        let tree_id = index.write_tree()?;
+        let tree = repo.find_tree(tree_id)?;
+
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        if let Some(parent) = &parent {
+            // nothing to commit, working tree clean
+            if parent.tree_id() == tree_id {
+                return Ok(())
+            }
+        }
+
+        let signature =
+            repo.signature().or_else(|_| git2::Signature::now("foundry", "foundry@noreply"))?;
+        let parents = parent.iter().collect::<Vec<_>>();
+        let parents = parents.iter().collect::<Vec<_>>();
+        repo.commit(Some("HEAD"), &signature, &signature, msg, &tree, &parents)?;
+        Ok(())
+    }
+
     pub fn is_in_repo(self) -> std::io::Result<bool> {
         self.cmd().args(["rev-parse", "--is-inside-work-tree"]).status().map(|s| s.success())
     }
 
     pub fn is_clean(self) -> Result<bool> {
+        if let GitBackend::Libgit2 = self.backend.resolve() {
+            let repo = git2::Repository::open(self.root)?;
+            let mut opts = git2::StatusOptions::new();
+            opts.include_untracked(true);
+            let statuses = repo.statuses(Some(&mut opts))?;
+            return Ok(statuses.iter().all(|entry| entry.status() == git2::Status::CURRENT))
+        }
         self.cmd().args(["status", "--porcelain"]).exec().map(|out| out.stdout.is_empty())
     }
 
@@ -444,6 +1060,11 @@ https://github.com/foundry-rs/foundry/issues/new/choose"
     }
 
     pub fn commit_hash(self, short: bool) -> Result<String> {
+        if let GitBackend::Libgit2 = self.backend.resolve() {
+            let repo = git2::Repository::open(self.root)?;
+            let id = repo.revparse_single("HEAD")?.id();
+            return Ok(if short { id.to_string()[..7].to_string() } else { id.to_string() })
+        }
         self.cmd().arg("rev-parse").args(short.then_some("--short")).arg("HEAD").get_stdout_lossy()
     }
 
@@ -456,6 +1077,17 @@ https://github.com/foundry-rs/foundry/issues/new/choose"
         I: IntoIterator<Item = S>,
         S: AsRef<OsStr>,
     {
+        if let GitBackend::Libgit2 = self.backend.resolve() {
+            let paths = paths
+                .into_iter()
+                .map(|p| p.as_ref().to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            let repo = git2::Repository::open(self.root)?;
+            return Ok(repo.submodules()?.iter().any(|sub| {
+                (paths.is_empty() || paths.iter().any(|p| sub.path().to_string_lossy() == *p)) &&
+                    sub.workdir_id().is_none()
+            }))
+        }
         self.cmd()
             .args(["submodule", "status"])
             .args(paths)
@@ -469,6 +1101,9 @@ https://github.com/foundry-rs/foundry/issues/new/choose"
         url: impl AsRef<OsStr>,
         path: impl AsRef<OsStr>,
     ) -> Result<()> {
+        if let GitBackend::Libgit2 = self.backend.resolve() {
+            return self.submodule_add_libgit2(url.as_ref(), path.as_ref())
+        }
         self.cmd()
             .stderr(self.stderr())
             .args(["submodule", "add"])
@@ -480,11 +1115,26 @@ https://github.com/foundry-rs/foundry/issues/new/choose"
             .map(drop)
     }
 
+    fn submodule_add_libgit2(self, url: &OsStr, path: &OsStr) -> Result<()> {
+        let url = url.to_str().ok_or_else(|| eyre::eyre!("submodule url is not valid UTF-8"))?;
+        let path = Path::new(path);
+        let repo = git2::Repository::open(self.root)?;
+        let mut submodule = repo.submodule(url, path, false)?;
+        let mut options = git2::SubmoduleUpdateOptions::new();
+        options.fetch(Self::fetch_options(self.shallow));
+        submodule.clone(Some(&mut options))?;
+        submodule.add_finalize()?;
+        Ok(())
+    }
+
     pub fn submodule_update<I, S>(self, force: bool, remote: bool, paths: I) -> Result<()>
     where
         I: IntoIterator<Item = S>,
         S: AsRef<OsStr>,
     {
+        if let GitBackend::Libgit2 = self.backend.resolve() {
+            return self.submodule_update_libgit2(force, remote, paths)
+        }
         self.cmd()
             .stderr(self.stderr())
             .args(["submodule", "update", "--progress", "--init", "--recursive"])
@@ -496,6 +1146,37 @@ https://github.com/foundry-rs/foundry/issues/new/choose"
             .map(drop)
     }
 
+    fn submodule_update_libgit2<I, S>(self, force: bool, remote: bool, paths: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let paths = paths
+            .into_iter()
+            .map(|p| p.as_ref().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        let repo = git2::Repository::open(self.root)?;
+        let mut options = git2::SubmoduleUpdateOptions::new();
+        options.fetch(Self::fetch_options(self.shallow));
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        if force {
+            checkout.force();
+        }
+        options.checkout(checkout);
+
+        for mut submodule in repo.submodules()? {
+            if !paths.is_empty() && !paths.iter().any(|p| submodule.path().to_string_lossy() == *p)
+            {
+                continue
+            }
+            if remote {
+                submodule.sync()?;
+            }
+            submodule.update(true, Some(&mut options))?;
+        }
+        Ok(())
+    }
+
     pub fn cmd(self) -> Command {
         let mut cmd = Self::cmd_no_root();
         cmd.current_dir(self.root);
@@ -561,4 +1242,173 @@ mod tests {
         assert_eq!(env::var("TESTCWDKEY").unwrap(), "cwd_val");
         assert_eq!(env::var("TESTPRJKEY").unwrap(), "prj_val");
     }
+
+    #[test]
+    fn libgit2_backend_init_add_commit_round_trip() {
+        let temp = tempdir().unwrap();
+        let git = Git::new(temp.path()).backend(GitBackend::Libgit2);
+        git.init().unwrap();
+        fs::create_file(temp.path().join("foundry.toml")).unwrap();
+
+        assert!(!git.is_clean().unwrap());
+        git.add(["foundry.toml"]).unwrap();
+        git.commit("init").unwrap();
+        assert!(git.is_clean().unwrap());
+
+        let hash = git.commit_hash(false).unwrap();
+        assert_eq!(hash.len(), 40);
+        assert_eq!(git.commit_hash(true).unwrap(), hash[..7]);
+
+        // committing again with nothing staged is a no-op, matching the CLI backend
+        git.commit("init").unwrap();
+        assert_eq!(git.commit_hash(false).unwrap(), hash);
+    }
+
+    fn aliases(entries: &[(&str, AliasValue)]) -> BTreeMap<String, AliasValue> {
+        entries.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+    }
+
+    fn argv(s: &str) -> Vec<String> {
+        s.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn resolve_aliases_expands_string_and_list_forms() {
+        let aliases = aliases(&[
+            ("tb", AliasValue::Single("build --sizes".to_string())),
+            ("cov", AliasValue::Multiple(vec!["test".to_string(), "--gas-report".to_string()])),
+        ]);
+
+        let out = resolve_aliases(argv("forge tb"), &aliases, &["build", "test"], &[]).unwrap();
+        assert_eq!(out, argv("forge build --sizes"));
+
+        let out = resolve_aliases(argv("forge cov"), &aliases, &["build", "test"], &[]).unwrap();
+        assert_eq!(out, argv("forge test --gas-report"));
+    }
+
+    #[test]
+    fn resolve_aliases_expands_transitively() {
+        let aliases = aliases(&[
+            ("a", AliasValue::Single("b".to_string())),
+            ("b", AliasValue::Single("build".to_string())),
+        ]);
+        let out = resolve_aliases(argv("forge a"), &aliases, &["build"], &[]).unwrap();
+        assert_eq!(out, argv("forge build"));
+    }
+
+    #[test]
+    fn resolve_aliases_rejects_cycles() {
+        let aliases = aliases(&[
+            ("a", AliasValue::Single("b".to_string())),
+            ("b", AliasValue::Single("a".to_string())),
+        ]);
+        assert!(resolve_aliases(argv("forge a"), &aliases, &[], &[]).is_err());
+    }
+
+    #[test]
+    fn resolve_aliases_builtin_subcommands_take_precedence() {
+        let aliases = aliases(&[("build", AliasValue::Single("test".to_string()))]);
+        let out = resolve_aliases(argv("forge build"), &aliases, &["build"], &[]).unwrap();
+        assert_eq!(out, argv("forge build"));
+    }
+
+    #[test]
+    fn resolve_aliases_skips_value_taking_flags() {
+        let aliases = aliases(&[("tb", AliasValue::Single("build --sizes".to_string()))]);
+        let out = resolve_aliases(
+            argv("forge --some-opt value tb"),
+            &aliases,
+            &["build"],
+            &["--some-opt"],
+        )
+        .unwrap();
+        assert_eq!(out, argv("forge --some-opt value build --sizes"));
+    }
+
+    #[test]
+    fn parse_rfc3339_round_trips_known_timestamp() {
+        // 2024-01-01T00:00:00Z is 1704067200 seconds after the Unix epoch
+        let parsed = parse_rfc3339("2024-01-01T00:00:00Z").unwrap();
+        assert_eq!(
+            parsed.duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs(),
+            1_704_067_200
+        );
+        assert!(parse_rfc3339("not-a-timestamp").is_none());
+    }
+
+    #[test]
+    fn parse_rfc3339_handles_numeric_offsets() {
+        // `credential_process` helpers (e.g. Python's `datetime.isoformat()`) commonly emit
+        // `+00:00` rather than `Z` for UTC, and other offsets for non-UTC-aware datetimes.
+        let utc = parse_rfc3339("2024-01-01T00:00:00Z").unwrap();
+        assert_eq!(parse_rfc3339("2024-01-01T00:00:00+00:00").unwrap(), utc);
+        assert_eq!(
+            parse_rfc3339("2024-01-01T02:00:00+02:00").unwrap(),
+            utc,
+            "a +02:00 local time of 02:00 is 00:00 UTC"
+        );
+        assert_eq!(
+            parse_rfc3339("2023-12-31T22:00:00-02:00").unwrap(),
+            utc,
+            "a -02:00 local time of 22:00 the day before is 00:00 UTC"
+        );
+    }
+
+    #[test]
+    fn parse_ini_section_reads_only_the_requested_section() {
+        let contents = "\
+[default]
+aws_access_key_id = AKIDEFAULT
+aws_secret_access_key = secretdefault
+
+[profile other]
+aws_access_key_id = AKIOTHER
+";
+        let default = parse_ini_section(contents, "default");
+        assert_eq!(default.get("aws_access_key_id").unwrap(), "AKIDEFAULT");
+        assert_eq!(default.get("aws_secret_access_key").unwrap(), "secretdefault");
+
+        let other = parse_ini_section(contents, "profile other");
+        assert_eq!(other.get("aws_access_key_id").unwrap(), "AKIOTHER");
+        assert!(other.get("aws_secret_access_key").is_none());
+    }
+
+    #[test]
+    fn fingerprint_round_trips_multiple_entries() {
+        let temp = tempdir().unwrap();
+        let a = temp.path().join("a.sol");
+        let b = temp.path().join("b.sol");
+        let c = temp.path().join("My Contract.sol");
+        fs::write(&a, "contract A {}").unwrap();
+        fs::write(&b, "contract B {}").unwrap();
+        fs::write(&c, "contract C {}").unwrap();
+
+        let fingerprint = Fingerprint::new([&a, &b, &c]).unwrap();
+        let sidecar = temp.path().join("fingerprint.d");
+        fingerprint.persist(&sidecar).unwrap();
+
+        let loaded = Fingerprint::load(&sidecar).unwrap().unwrap();
+        assert_eq!(loaded.entries.len(), 3);
+        assert_eq!(loaded.entries, fingerprint.entries);
+        assert!(loaded.is_fresh());
+
+        fs::write(&b, "contract B { uint256 x; }").unwrap();
+        let loaded = Fingerprint::load(&sidecar).unwrap().unwrap();
+        assert!(!loaded.is_fresh());
+    }
+
+    #[test]
+    fn fingerprint_load_returns_none_when_missing() {
+        let temp = tempdir().unwrap();
+        assert!(Fingerprint::load(&temp.path().join("missing.d")).unwrap().is_none());
+    }
+
+    #[test]
+    fn dep_info_path_escaping_round_trips() {
+        let escaped = escape_dep_info_path("My Contracts/Token.sol");
+        assert_eq!(escaped, "My\\ Contracts/Token.sol");
+        assert_eq!(unescape_dep_info_path(&escaped).unwrap(), "My Contracts/Token.sol");
+
+        assert!(unescape_dep_info_path("dangling\\").is_err());
+    }
 }